@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+
+/// The public, rendered form of a request body: what a [`crate::Schema`]'s
+/// internal `[body]` table is converted into for the placeholder renderer.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum Body {
+    #[default]
+    None,
+    Raw {
+        content: String,
+        mime: mime::Mime,
+    },
+    Binary {
+        path: String,
+        mime: mime::Mime,
+    },
+    FormData(HashMap<String, FormDataValue>),
+    XFormUrlEncoded(HashMap<String, Vec<String>>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormDataValue {
+    Text(String),
+    File(String),
+}
@@ -0,0 +1,46 @@
+use std::fmt;
+
+/// Errors produced while parsing or validating a [`crate::Schema`].
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The schema's TOML failed to parse.
+    Toml(String),
+    /// A field holds a TOML value type that has no sane string
+    /// representation in an HTTP request (e.g. a bare datetime).
+    InvalidType { field: String, invalid_type: String },
+    /// A field's value failed an attached [`crate::validate::Rule`].
+    Validation {
+        field: String,
+        rule: String,
+        value: String,
+    },
+    /// A `[*.validate]` rule expression failed to parse.
+    InvalidRule(String),
+    /// `[http].method` isn't a valid HTTP method token.
+    InvalidMethod(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Toml(message) => write!(f, "{message}"),
+            Error::InvalidType {
+                field,
+                invalid_type,
+            } => write!(f, "{field}: invalid type `{invalid_type}`"),
+            Error::Validation { field, rule, value } => {
+                write!(f, "{field}: value `{value}` failed rule {rule}")
+            }
+            Error::InvalidRule(rule) => write!(f, "invalid validation rule: {rule}"),
+            Error::InvalidMethod(method) => write!(f, "invalid HTTP method: {method}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<toml::de::Error> for Error {
+    fn from(err: toml::de::Error) -> Self {
+        Error::Toml(err.to_string())
+    }
+}
@@ -0,0 +1,57 @@
+pub mod body;
+mod error;
+mod request;
+mod schema;
+pub mod validate;
+
+pub use body::{Body, FormDataValue};
+pub use error::Error;
+pub use request::Request;
+
+use std::str::FromStr;
+
+/// Parses a schema's TOML source and renders it into a [`Request`], with
+/// its `[query_params]`/`[body.form_data]` rules checked against literal
+/// values. Values that still carry a placeholder are not checked, and the
+/// rules are not carried into the returned [`Request`].
+pub fn parse(toml: &str) -> Result<Request, Error> {
+    schema::Schema::from_str(toml)?.into_request()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_renders_a_request() {
+        let toml = r#"
+        [http]
+        method = "POST"
+        url = "https://example.org/api"
+
+        [cookies]
+        session = "abc123"
+
+        [queryparams]
+        page = 1
+        "#;
+        let request = parse(toml).unwrap();
+        assert_eq!(request.method, http::Method::POST);
+        assert_eq!(request.url, "https://example.org/api");
+        assert_eq!(request.cookies.get("session").unwrap(), "abc123");
+        assert_eq!(
+            request.query_params,
+            vec![("page".to_string(), "1".to_string())]
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_method() {
+        let toml = r#"
+        [http]
+        method = "INVALID METHOD"
+        url = "https://example.org/api"
+        "#;
+        assert!(matches!(parse(toml), Err(Error::InvalidMethod(_))));
+    }
+}
@@ -0,0 +1,18 @@
+use crate::Body;
+use http::{HeaderMap, Method, Version};
+use std::collections::HashMap;
+
+/// A fully-resolved HTTP request: produced by rendering a [`crate::Schema`]
+/// with its placeholders substituted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub http_version: Version,
+    pub metadata: HashMap<String, String>,
+    pub headers: HeaderMap,
+    pub query_params: Vec<(String, String)>,
+    pub variables: HashMap<String, String>,
+    pub cookies: HashMap<String, String>,
+    pub body: Body,
+}
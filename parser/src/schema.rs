@@ -1,11 +1,20 @@
+mod body;
+mod cookies;
 mod query_params;
+mod table;
+mod types;
 mod validation;
 
+pub(crate) use body::Body;
+pub(crate) use cookies::Cookies;
 pub(crate) use query_params::QueryParams;
 
 use crate::error::Error;
-use crate::schema::validation::validate_types;
+use crate::request::Request;
+use crate::schema::validation::{validate_types, validate_values, value_to_string};
+use http::{HeaderMap, Method, Version};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::str::FromStr;
 
 #[derive(Deserialize)]
@@ -13,6 +22,9 @@ pub(crate) struct Schema {
     pub http: Http,
     #[serde(alias = "queryparams", alias = "query-params")]
     pub query_params: Option<QueryParams>,
+    pub cookies: Option<Cookies>,
+    #[serde(default)]
+    pub body: Body,
 }
 
 #[derive(Deserialize)]
@@ -31,6 +43,8 @@ impl Schema {
                 method: "GET".to_string(),
             },
             query_params: None,
+            cookies: None,
+            body: Body::default(),
         }
     }
 }
@@ -41,6 +55,7 @@ impl FromStr for Schema {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let schema: Schema = toml::from_str(s)?;
         validate_types(&schema)?;
+        validate_values(&schema)?;
         Ok(schema)
     }
 }
@@ -50,6 +65,43 @@ fn default_method() -> String {
     "GET".to_string()
 }
 
+impl Schema {
+    /// Renders this schema into a [`Request`]. Placeholders are left
+    /// unresolved here. The `[query_params.validate]` and
+    /// `[body.form_data.validate]` rules are not carried into the returned
+    /// [`Request`]: a render-time re-check covers only the rules given to the
+    /// placeholders crate's `Renderer::with_rules`.
+    pub(crate) fn into_request(self) -> Result<Request, Error> {
+        let method = Method::from_str(&self.http.method)
+            .map_err(|_| Error::InvalidMethod(self.http.method.clone()))?;
+
+        let query_params = self
+            .query_params
+            .map(|query_params| {
+                query_params
+                    .0
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value_to_string(value)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let cookies = self.cookies.map(|cookies| cookies.0).unwrap_or_default();
+
+        Ok(Request {
+            method,
+            url: self.http.url,
+            http_version: Version::HTTP_11,
+            metadata: HashMap::new(),
+            headers: HeaderMap::new(),
+            query_params,
+            variables: HashMap::new(),
+            cookies,
+            body: self.body.into(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -94,6 +146,23 @@ mod test {
         );
     }
 
+    #[test]
+    fn deserialize_cookies() {
+        let toml = r#"
+        [http]
+        url = "https://example.org/api"
+
+        [cookies]
+        session = "abc123"
+        theme = "dark"
+        "#;
+        let mut schema: Schema = toml::from_str(toml).unwrap();
+        let cookies = schema.cookies.take().unwrap();
+        assert_eq!(cookies.0.len(), 2);
+        assert_eq!(cookies.0.get("session").unwrap(), "abc123");
+        assert_eq!(cookies.0.get("theme").unwrap(), "dark");
+    }
+
     #[test]
     fn missing_fields() {
         assert!(Schema::from_str("")
@@ -115,6 +184,39 @@ mod test {
         assert_eq!(schema.http.method, "GET");
     }
 
+    #[test]
+    fn into_request_maps_method_query_params_and_cookies() {
+        let toml = r#"
+        [http]
+        method = "POST"
+        url = "https://example.org/api"
+
+        [cookies]
+        session = "abc123"
+
+        [queryparams]
+        page = 1
+        "#;
+        let request = Schema::from_str(toml).unwrap().into_request().unwrap();
+        assert_eq!(request.method, http::Method::POST);
+        assert_eq!(request.url, "https://example.org/api");
+        assert_eq!(request.cookies.get("session").unwrap(), "abc123");
+        assert_eq!(
+            request.query_params,
+            vec![("page".to_string(), "1".to_string())]
+        );
+    }
+
+    #[test]
+    fn into_request_rejects_invalid_method() {
+        let mut schema = Schema::new();
+        schema.http.method = "INVALID METHOD".to_string();
+        assert!(matches!(
+            schema.into_request(),
+            Err(Error::InvalidMethod(_))
+        ));
+    }
+
     #[test]
     fn invalid_type() {
         let toml = r#"
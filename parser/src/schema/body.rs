@@ -47,7 +47,9 @@ impl From<Body> for PublicBody {
                 mime: mime::APPLICATION_OCTET_STREAM,
             },
             Body::FormData(table) => PublicBody::FormData(table.into_map()),
-            Body::XFormUrlEncoded(table) => PublicBody::XFormUrlEncoded(table.into_map()),
+            // `into_expanded_map` fans a `PrimitiveArray::Multiple` value out into
+            // repeated `key=v1&key=v2` entries instead of collapsing it to one.
+            Body::XFormUrlEncoded(table) => PublicBody::XFormUrlEncoded(table.into_expanded_map()),
         }
     }
 }
@@ -0,0 +1,5 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub(crate) struct Cookies(pub HashMap<String, String>);
@@ -0,0 +1,53 @@
+use crate::validate::Rule;
+use serde::de::{self, MapAccess, Visitor};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use toml::Value;
+
+/// Query-param values, keyed by name, alongside any `[query_params.validate]`
+/// rules attached to those names. `validate` is a reserved key: it is parsed
+/// out of the table rather than treated as a param named "validate".
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct QueryParams(pub HashMap<String, Value>, pub HashMap<String, Rule>);
+
+impl<'de> Deserialize<'de> for QueryParams {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct QueryParamsVisitor;
+
+        impl<'de> Visitor<'de> for QueryParamsVisitor {
+            type Value = QueryParams;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a table of query param values")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut values = HashMap::new();
+                let mut rules = HashMap::new();
+
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "validate" {
+                        let raw: HashMap<String, String> = map.next_value()?;
+                        for (field, expr) in raw {
+                            let rule = expr.parse().map_err(de::Error::custom)?;
+                            rules.insert(field, rule);
+                        }
+                    } else {
+                        values.insert(key, map.next_value()?);
+                    }
+                }
+
+                Ok(QueryParams(values, rules))
+            }
+        }
+
+        deserializer.deserialize_map(QueryParamsVisitor)
+    }
+}
@@ -0,0 +1,178 @@
+use crate::schema::body::FormDataValue;
+use crate::schema::types::PrimitiveArray;
+use crate::validate::Rule;
+use serde::de::{self, MapAccess, Visitor};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// Converts a table's raw value type into the public, rendered-request
+/// value type it carries once parsing is done.
+pub(crate) trait Transform<V, Out> {
+    fn map_value(value: V) -> Out;
+}
+
+/// Form-data fields, alongside any `[body.form_data.validate]` rules
+/// attached to those field names. `validate` is a reserved key: it is
+/// parsed out of the table rather than treated as a field named "validate".
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct FormDataTable(pub HashMap<String, FormDataValue>, pub HashMap<String, Rule>);
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub(crate) struct PrimitiveTable(pub HashMap<String, PrimitiveArray>);
+
+impl<'de> Deserialize<'de> for FormDataTable {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct FormDataTableVisitor;
+
+        impl<'de> Visitor<'de> for FormDataTableVisitor {
+            type Value = FormDataTable;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a table of form-data fields")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut values = HashMap::new();
+                let mut rules = HashMap::new();
+
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "validate" {
+                        let raw: HashMap<String, String> = map.next_value()?;
+                        for (field, expr) in raw {
+                            let rule = expr.parse().map_err(de::Error::custom)?;
+                            rules.insert(field, rule);
+                        }
+                    } else {
+                        values.insert(key, map.next_value()?);
+                    }
+                }
+
+                Ok(FormDataTable(values, rules))
+            }
+        }
+
+        deserializer.deserialize_map(FormDataTableVisitor)
+    }
+}
+
+impl FormDataTable {
+    /// The `[body.form_data.validate]` rules attached to field names.
+    pub(crate) fn validate(&self) -> &HashMap<String, Rule> {
+        &self.1
+    }
+}
+
+impl Deref for FormDataTable {
+    type Target = HashMap<String, FormDataValue>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for FormDataTable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Deref for PrimitiveTable {
+    type Target = HashMap<String, PrimitiveArray>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for PrimitiveTable {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FormDataTable {
+    /// Converts every value via [`Transform::map_value`], expanding dotted or
+    /// bracketed field names (`user.address.city`) to the bracket convention
+    /// (`user[address][city]`) a multipart form body expects.
+    pub(crate) fn into_map<Out>(self) -> HashMap<String, Out>
+    where
+        Self: Transform<FormDataValue, Out>,
+    {
+        self.0
+            .into_iter()
+            .map(|(key, value)| (bracketify(&key), Self::map_value(value)))
+            .collect()
+    }
+}
+
+impl PrimitiveTable {
+    /// Expands every value into its own `key=value` entries (so a TOML array
+    /// fans out rather than collapsing to one value) and expands dotted or
+    /// bracketed field names to the bracket convention.
+    pub(crate) fn into_expanded_map(self) -> HashMap<String, Vec<String>> {
+        self.0
+            .into_iter()
+            .map(|(key, value)| (bracketify(&key), value.into_values()))
+            .collect()
+    }
+}
+
+/// Expands a dotted field name (`user.address.city`) into the bracket
+/// convention (`user[address][city]`). Keys that are already bracketed or
+/// have no dots (including array-style keys like `tags[]`) pass through
+/// unchanged.
+fn bracketify(key: &str) -> String {
+    if !key.contains('.') || key.contains('[') {
+        return key.to_string();
+    }
+
+    let mut segments = key.split('.');
+    let first = segments.next().unwrap_or_default().to_string();
+    segments.fold(first, |acc, segment| format!("{acc}[{segment}]"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::types::Primitive;
+
+    #[test]
+    fn bracketify_expands_dotted_keys() {
+        assert_eq!(bracketify("user.address.city"), "user[address][city]");
+    }
+
+    #[test]
+    fn bracketify_leaves_bracketed_and_plain_keys_untouched() {
+        assert_eq!(bracketify("tags[]"), "tags[]");
+        assert_eq!(bracketify("tags"), "tags");
+    }
+
+    #[test]
+    fn into_expanded_map_fans_out_arrays_and_expands_keys() {
+        let mut table = HashMap::new();
+        table.insert(
+            "tags".to_string(),
+            PrimitiveArray::Multiple(vec![
+                Primitive::Str("a".to_string()),
+                Primitive::Str("b".to_string()),
+            ]),
+        );
+        table.insert(
+            "user.address.city".to_string(),
+            PrimitiveArray::Single(Primitive::Str("NYC".to_string())),
+        );
+
+        let map = PrimitiveTable(table).into_expanded_map();
+
+        assert_eq!(map["tags"], vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(map["user[address][city]"], vec!["NYC".to_string()]);
+    }
+}
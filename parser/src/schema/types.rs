@@ -0,0 +1,74 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum Primitive {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+impl Primitive {
+    fn into_value_string(self) -> String {
+        match self {
+            Primitive::Bool(b) => b.to_string(),
+            Primitive::Int(i) => i.to_string(),
+            Primitive::Float(f) => f.to_string(),
+            Primitive::Str(s) => s,
+        }
+    }
+}
+
+/// A TOML array value (`tags = ["a", "b"]`) or a single scalar. In an
+/// urlencoded body it is serialized as repeated `key=v1&key=v2` pairs; in a
+/// multipart form-data text field it is joined into one `,`-separated value.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum PrimitiveArray {
+    Multiple(Vec<Primitive>),
+    Single(Primitive),
+}
+
+impl PrimitiveArray {
+    /// Expands this value into one entry per value it carries, preserving
+    /// order and never collapsing a `Multiple` down to a single string.
+    pub(crate) fn into_values(self) -> Vec<String> {
+        match self {
+            PrimitiveArray::Single(value) => vec![value.into_value_string()],
+            PrimitiveArray::Multiple(values) => {
+                values.into_iter().map(Primitive::into_value_string).collect()
+            }
+        }
+    }
+}
+
+impl From<PrimitiveArray> for String {
+    /// Used where only a single value makes sense (e.g. a multipart text
+    /// field); a `Multiple` value is joined with `,` rather than dropped.
+    fn from(value: PrimitiveArray) -> Self {
+        value.into_values().join(",")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn single_into_values() {
+        assert_eq!(
+            PrimitiveArray::Single(Primitive::Int(1)).into_values(),
+            vec!["1".to_string()]
+        );
+    }
+
+    #[test]
+    fn multiple_into_values_does_not_collapse() {
+        let array = PrimitiveArray::Multiple(vec![
+            Primitive::Str("a".to_string()),
+            Primitive::Str("b".to_string()),
+        ]);
+        assert_eq!(array.into_values(), vec!["a".to_string(), "b".to_string()]);
+    }
+}
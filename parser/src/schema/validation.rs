@@ -0,0 +1,120 @@
+use crate::error::Error;
+use crate::schema::body::{Body, FormDataValue};
+use crate::schema::Schema;
+use toml::Value;
+
+/// Rejects TOML value types that have no sane string representation in an
+/// HTTP request (e.g. bare datetimes), before anything is rendered.
+pub(crate) fn validate_types(schema: &Schema) -> Result<(), Error> {
+    if let Some(query_params) = &schema.query_params {
+        for value in query_params.0.values() {
+            check_type(value, "params of [query_params]")?;
+        }
+    }
+    Ok(())
+}
+
+fn check_type(value: &Value, field: &str) -> Result<(), Error> {
+    match value {
+        Value::Datetime(_) => Err(Error::InvalidType {
+            field: field.to_string(),
+            invalid_type: "datetime".to_string(),
+        }),
+        Value::Array(values) => values.iter().try_for_each(|value| check_type(value, field)),
+        _ => Ok(()),
+    }
+}
+
+/// Checks every query-param and form-data value against the
+/// `[query_params.validate]` / `[body.form_data.validate]` rule attached to
+/// its name, once the literal schema value is known. A value that still
+/// carries a placeholder is skipped here; it is checked at render time only
+/// if the same rule is given to `Renderer::with_rules` for
+/// `Renderer::render_validated`.
+pub(crate) fn validate_values(schema: &Schema) -> Result<(), Error> {
+    if let Some(query_params) = &schema.query_params {
+        for (field, rule) in &query_params.1 {
+            if let Some(value) = query_params.0.get(field) {
+                let value = value_to_string(value);
+                if has_placeholder(&value) {
+                    continue;
+                }
+                rule.check(field, &value)?;
+            }
+        }
+    }
+
+    if let Body::FormData(form) = &schema.body {
+        for (field, rule) in form.validate() {
+            if let Some(value) = form.get(field) {
+                let value = form_value_to_string(value);
+                if has_placeholder(&value) {
+                    continue;
+                }
+                rule.check(field, &value)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `value` still carries an unresolved `{{...}}` placeholder token.
+/// A value like this can't be checked against its rule yet — the real value
+/// is only known once a renderer substitutes it — so the static check here
+/// skips it instead of failing to parse a schema that legitimately uses a
+/// placeholder on a validated field.
+fn has_placeholder(value: &str) -> bool {
+    value.contains("{{") && value.contains("}}")
+}
+
+pub(crate) fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn form_value_to_string(value: &FormDataValue) -> String {
+    match value {
+        FormDataValue::Text(primitive) => primitive.clone().into(),
+        FormDataValue::File(path) => path.clone(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::schema::QueryParams;
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    #[test]
+    fn validate_values_rejects_out_of_range() {
+        let mut values = HashMap::new();
+        values.insert("page".to_string(), Value::Integer(500));
+        let mut rules = HashMap::new();
+        rules.insert("page".to_string(), crate::validate::Rule::from_str("range(0..100)").unwrap());
+
+        let mut schema = Schema::new();
+        schema.query_params = Some(QueryParams(values, rules));
+
+        assert!(matches!(
+            validate_values(&schema),
+            Err(Error::Validation { field, .. }) if field == "page"
+        ));
+    }
+
+    #[test]
+    fn validate_values_defers_placeholder_values_to_render_time() {
+        let mut values = HashMap::new();
+        values.insert("page".to_string(), Value::String("{{page}}".to_string()));
+        let mut rules = HashMap::new();
+        rules.insert("page".to_string(), crate::validate::Rule::from_str("range(0..100)").unwrap());
+
+        let mut schema = Schema::new();
+        schema.query_params = Some(QueryParams(values, rules));
+
+        assert!(validate_values(&schema).is_ok());
+    }
+}
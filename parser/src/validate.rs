@@ -0,0 +1,185 @@
+use crate::error::Error;
+use regex::Regex;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// An ad-hoc constraint attached to a query-param or form field, e.g.
+/// `len(1..=64)`, `range(0..=100)`, `one_of("asc","desc")`, `matches(regex)`.
+///
+/// Checked once against the schema's literal value (see `validate_values`),
+/// and again by the placeholders crate's `Renderer::render_validated`, once
+/// the real, placeholder-substituted value is known, when the rule is given
+/// to it through `Renderer::with_rules`.
+#[derive(Debug, Clone)]
+pub enum Rule {
+    Len(RangeInclusive<usize>),
+    Range(RangeInclusive<i64>),
+    OneOf(Vec<String>),
+    Matches(Regex),
+}
+
+impl PartialEq for Rule {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Rule::Len(a), Rule::Len(b)) => a == b,
+            (Rule::Range(a), Rule::Range(b)) => a == b,
+            (Rule::OneOf(a), Rule::OneOf(b)) => a == b,
+            (Rule::Matches(a), Rule::Matches(b)) => a.as_str() == b.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Rule {
+    /// Checks `value` against this rule, returning [`Error::Validation`] on failure.
+    pub fn check(&self, field: &str, value: &str) -> Result<(), Error> {
+        let ok = match self {
+            Rule::Len(range) => range.contains(&value.len()),
+            Rule::Range(range) => value.parse::<i64>().is_ok_and(|n| range.contains(&n)),
+            Rule::OneOf(options) => options.iter().any(|option| option == value),
+            Rule::Matches(regex) => regex.is_match(value),
+        };
+        if ok {
+            Ok(())
+        } else {
+            Err(Error::Validation {
+                field: field.to_string(),
+                rule: self.to_string(),
+                value: value.to_string(),
+            })
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Len(range) => write!(f, "len({}..={})", range.start(), range.end()),
+            Rule::Range(range) => write!(f, "range({}..={})", range.start(), range.end()),
+            Rule::OneOf(options) => {
+                let options = options
+                    .iter()
+                    .map(|option| format!("{option:?}"))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                write!(f, "one_of({options})")
+            }
+            Rule::Matches(regex) => write!(f, "matches({})", regex.as_str()),
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidRule(s.to_string());
+        let (name, args) = s.split_once('(').ok_or_else(invalid)?;
+        let args = args.strip_suffix(')').ok_or_else(invalid)?;
+
+        match name {
+            "len" => parse_usize_range(args).ok_or_else(invalid).map(Rule::Len),
+            "range" => parse_i64_range(args).ok_or_else(invalid).map(Rule::Range),
+            "one_of" => Ok(Rule::OneOf(
+                args.split(',').map(|value| unquote(value.trim())).collect(),
+            )),
+            "matches" => Regex::new(&unquote(args.trim()))
+                .map(Rule::Matches)
+                .map_err(|_| invalid()),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+fn parse_usize_range(args: &str) -> Option<RangeInclusive<usize>> {
+    let (start, end) = args.split_once("..=")?;
+    Some(start.trim().parse().ok()?..=end.trim().parse().ok()?)
+}
+
+fn parse_i64_range(args: &str) -> Option<RangeInclusive<i64>> {
+    if let Some((start, end)) = args.split_once("..=") {
+        return Some(start.trim().parse().ok()?..=end.trim().parse().ok()?);
+    }
+    let (start, end) = args.split_once("..")?;
+    let end: i64 = end.trim().parse().ok()?;
+    Some(start.trim().parse().ok()?..=end - 1)
+}
+
+fn unquote(value: &str) -> String {
+    value.trim_matches('"').to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_len() {
+        assert_eq!("len(1..=64)".parse(), Ok(Rule::Len(1..=64)));
+    }
+
+    #[test]
+    fn parse_range_exclusive_and_inclusive() {
+        assert_eq!("range(0..100)".parse(), Ok(Rule::Range(0..=99)));
+        assert_eq!("range(0..=100)".parse(), Ok(Rule::Range(0..=100)));
+    }
+
+    #[test]
+    fn parse_one_of() {
+        assert_eq!(
+            "one_of(\"asc\",\"desc\")".parse(),
+            Ok(Rule::OneOf(vec!["asc".to_string(), "desc".to_string()]))
+        );
+    }
+
+    #[test]
+    fn parse_matches() {
+        assert_eq!(
+            "matches(\"^[a-z]+$\")".parse(),
+            Ok(Rule::Matches(Regex::new("^[a-z]+$").unwrap()))
+        );
+    }
+
+    #[test]
+    fn parse_invalid_rule() {
+        assert!("nonsense".parse::<Rule>().is_err());
+    }
+
+    #[test]
+    fn parse_matches_rejects_invalid_regex() {
+        assert_eq!(
+            "matches(\"[\")".parse::<Rule>(),
+            Err(Error::InvalidRule("matches(\"[\")".to_string()))
+        );
+    }
+
+    #[test]
+    fn check_len() {
+        let rule = Rule::Len(1..=4);
+        assert!(rule.check("field", "abcd").is_ok());
+        assert!(rule.check("field", "abcde").is_err());
+    }
+
+    #[test]
+    fn check_range() {
+        let rule = Rule::Range(0..=99);
+        assert!(rule.check("field", "50").is_ok());
+        assert!(rule.check("field", "100").is_err());
+        assert!(rule.check("field", "not-a-number").is_err());
+    }
+
+    #[test]
+    fn check_one_of() {
+        let rule = Rule::OneOf(vec!["asc".to_string(), "desc".to_string()]);
+        assert!(rule.check("field", "asc").is_ok());
+        assert!(rule.check("field", "ascending").is_err());
+    }
+
+    #[test]
+    fn check_matches() {
+        let rule = Rule::Matches(Regex::new("^[a-z]+$").unwrap());
+        assert!(rule.check("field", "lowercase").is_ok());
+        assert!(rule.check("field", "Uppercase").is_err());
+    }
+}
@@ -0,0 +1,6 @@
+mod placeholders;
+mod renderer;
+mod scan;
+
+pub use placeholders::{Location, Placeholders};
+pub use renderer::{RenderOptions, Renderer};
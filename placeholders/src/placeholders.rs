@@ -0,0 +1,36 @@
+use http::HeaderName;
+use std::collections::HashMap;
+
+/// Where a placeholder was found (or should be substituted) in a [`rede_schema::Request`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Location {
+    Url,
+    Headers(HeaderName),
+    QueryParams(String),
+    BodyForm(String),
+    Cookies(String),
+    Body,
+}
+
+/// Every `{{identifier}}` placeholder found in a request, keyed by variable
+/// name, alongside every [`Location`] it occurs in (including duplicates, so
+/// a name appearing twice in the same location is recorded twice).
+#[derive(Debug, Default)]
+pub struct Placeholders(HashMap<String, Vec<Location>>);
+
+impl Placeholders {
+    /// Records `location` against every name in `names`.
+    pub fn add_all(&mut self, location: &Location, names: Vec<&str>) {
+        for name in names {
+            self.0
+                .entry(name.to_string())
+                .or_default()
+                .push(location.clone());
+        }
+    }
+
+    /// Iterates over every placeholder name and the locations it occurs in.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Vec<Location>)> {
+        self.0.iter()
+    }
+}
@@ -3,12 +3,23 @@ use crate::Placeholders;
 use http::{HeaderMap, HeaderName};
 use miette::{miette, Result};
 use rede_schema::body::FormDataValue;
+use rede_schema::validate::Rule;
 use rede_schema::{Body, Request};
 use std::collections::HashMap;
 
 pub struct Renderer {
     placeholders: Placeholders,
     values_map: HashMap<String, String>,
+    options: RenderOptions,
+    rules: HashMap<Location, Rule>,
+}
+
+/// Tunables for [`Renderer::render`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+    /// Skip percent-encoding of substituted query-param, urlencoded-form and
+    /// cookie values, for callers who already pre-encoded them.
+    pub skip_percent_encoding: bool,
 }
 
 macro_rules! replace_pointer {
@@ -30,9 +41,30 @@ impl Renderer {
         Self {
             placeholders,
             values_map,
+            options: RenderOptions::default(),
+            rules: HashMap::new(),
         }
     }
 
+    /// Overrides the default [`RenderOptions`] used during [`Renderer::render`].
+    #[must_use]
+    pub fn with_options(mut self, options: RenderOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Attaches per-field [`Rule`]s (keyed by the [`Location`] they were
+    /// declared against, e.g. `[query_params.validate]`) for
+    /// [`Renderer::render_validated`] to re-check once the real, substituted
+    /// value is known. Keying by `Location` (rather than field name alone)
+    /// keeps a `query_params` rule from colliding with a `body.form_data`
+    /// rule that happens to share the same field name.
+    #[must_use]
+    pub fn with_rules(mut self, rules: HashMap<Location, Rule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
     /// todo doc
     ///
     /// # Errors
@@ -47,6 +79,7 @@ impl Renderer {
         let mut headers = request.headers;
         let mut query_params = request.query_params;
         let mut body = request.body;
+        let mut cookies = request.cookies;
 
         for (key, locations) in self.placeholders.iter() {
             let val = self.values_map.get(key); // todo maybe this could be changed into a map
@@ -60,6 +93,7 @@ impl Renderer {
                         }
                         Location::QueryParams(key) => {
                             if let Some((_, v)) = query_params.iter_mut().find(|(k, _)| k == key) {
+                                let val = &self.encode(val);
                                 replace_pointer!(v, &placeholder, val);
                             }
                         }
@@ -68,16 +102,40 @@ impl Renderer {
                                 render_form_data(form, k, &placeholder, val);
                             }
                             Body::XFormUrlEncoded(form) => {
+                                let val = &self.encode(val);
                                 render_form_urlencoded(form, k, &placeholder, val);
                             }
                             _ => panic!("unexpected body type"),
                         },
-                        Location::Body => { /* todo */ }
+                        Location::Cookies(name) => {
+                            if let Some(v) = cookies.get_mut(name) {
+                                let val = &self.encode_cookie(val);
+                                replace_pointer!(v, &placeholder, val);
+                            }
+                        }
+                        Location::Body => match &mut body {
+                            Body::Raw { content, mime } => {
+                                let val = if is_json(mime) {
+                                    json_escape(val)
+                                } else {
+                                    val.to_string()
+                                };
+                                replace_pointer!(content, &placeholder, &val);
+                            }
+                            Body::Binary { path, .. } => {
+                                replace_pointer!(path, &placeholder, val);
+                            }
+                            _ => panic!("unexpected body type"),
+                        },
                     }
                 }
             }
         }
 
+        if !cookies.is_empty() {
+            render_cookie_header(&mut headers, &cookies)?;
+        }
+
         Ok(Request {
             method: request.method,
             url,
@@ -86,9 +144,136 @@ impl Renderer {
             headers,
             query_params,
             variables: request.variables,
+            cookies,
             body,
         })
     }
+
+    /// Renders `request`, then re-checks every rule attached via
+    /// [`Renderer::with_rules`] against the values actually substituted,
+    /// since placeholders mean the real value is often only known here.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if rendering fails, or if a substituted value fails
+    /// its attached validation rule.
+    pub fn render_validated(&self, request: Request) -> Result<Request> {
+        let rendered = self.render(request)?;
+
+        // `self.rules` is keyed by (Location, field name), not placeholder
+        // variable name, so rules are checked against the rendered/resolved
+        // value for that field — the value a placeholder substituted into it
+        // is often only known here. Keying by Location keeps a query-params
+        // rule from being checked against a same-named body.form_data field.
+        for (field, value) in &rendered.query_params {
+            if let Some(rule) = self.rules.get(&Location::QueryParams(field.clone())) {
+                rule.check(field, value).map_err(|err| miette!("{err}"))?;
+            }
+        }
+
+        match &rendered.body {
+            Body::XFormUrlEncoded(form) => {
+                for (field, values) in form {
+                    if let Some(rule) = self.rules.get(&Location::BodyForm(field.clone())) {
+                        for value in values {
+                            rule.check(field, value).map_err(|err| miette!("{err}"))?;
+                        }
+                    }
+                }
+            }
+            Body::FormData(form) => {
+                for (field, value) in form {
+                    if let Some(rule) = self.rules.get(&Location::BodyForm(field.clone())) {
+                        let FormDataValue::Text(value) | FormDataValue::File(value) = value;
+                        rule.check(field, value).map_err(|err| miette!("{err}"))?;
+                    }
+                }
+            }
+            Body::Raw { .. } | Body::Binary { .. } | Body::None => {}
+        }
+
+        Ok(rendered)
+    }
+
+    /// Percent-encodes `val` per `application/x-www-form-urlencoded`, unless
+    /// [`RenderOptions::skip_percent_encoding`] is set.
+    fn encode(&self, val: &str) -> String {
+        if self.options.skip_percent_encoding {
+            val.to_string()
+        } else {
+            percent_encode_form(val)
+        }
+    }
+
+    /// Percent-encodes `val` for safe inclusion in a `Cookie` header pair,
+    /// unless [`RenderOptions::skip_percent_encoding`] is set. A raw `;` or
+    /// `=` in a substituted value would otherwise inject extra cookie pairs
+    /// or corrupt the header `render_cookie_header` builds.
+    fn encode_cookie(&self, val: &str) -> String {
+        if self.options.skip_percent_encoding {
+            val.to_string()
+        } else {
+            percent_encode_cookie(val)
+        }
+    }
+}
+
+/// Encodes `value` per the `application/x-www-form-urlencoded` rule: spaces
+/// become `+`, and everything outside `A-Za-z0-9-_.~` is escaped as `%XX`.
+fn percent_encode_form(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Percent-encodes `value` for safe inclusion in a `Cookie` header pair:
+/// everything outside `A-Za-z0-9-_.~` is escaped as `%XX`, including space
+/// (unlike [`percent_encode_form`], a cookie value is not
+/// `application/x-www-form-urlencoded` data, so `+` stays literal).
+fn percent_encode_cookie(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Whether `mime` is a JSON body: bare `application/json`, or a
+/// structured-syntax type that carries the `+json` suffix (e.g.
+/// `application/problem+json`, `application/ld+json`).
+fn is_json(mime: &mime::Mime) -> bool {
+    mime.subtype() == mime::JSON || mime.suffix() == Some(mime::JSON)
+}
+
+/// Escapes `val` so it can be substituted into a JSON string literal without
+/// producing invalid JSON (escapes `"`, `\`, and control characters).
+fn json_escape(val: &str) -> String {
+    let mut escaped = String::with_capacity(val.len());
+    for c in val.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 fn render_headers(
@@ -109,6 +294,45 @@ fn render_headers(
     Ok(())
 }
 
+/// Serializes `cookies` into a single `Cookie` request header, merging with
+/// (and overriding) any `Cookie` header the user already set.
+fn render_cookie_header(header_map: &mut HeaderMap, cookies: &HashMap<String, String>) -> Result<()> {
+    let mut pairs: Vec<(String, String)> = header_map
+        .get(http::header::COOKIE)
+        .and_then(|v| v.to_str().ok())
+        .map(|s| {
+            s.split(';')
+                .filter_map(|pair| {
+                    let (name, value) = pair.split_once('=')?;
+                    Some((name.trim().to_string(), value.trim().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for (name, value) in cookies {
+        match pairs.iter_mut().find(|(n, _)| n == name) {
+            Some((_, v)) => *v = value.clone(),
+            None => pairs.push((name.clone(), value.clone())),
+        }
+    }
+    pairs.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let header_value = pairs
+        .iter()
+        .map(|(name, value)| format!("{name}={value}"))
+        .collect::<Vec<_>>()
+        .join("; ");
+
+    header_map.insert(
+        http::header::COOKIE,
+        header_value
+            .parse()
+            .map_err(|_| miette!("rendered cookie header is invalid: {header_value}"))?,
+    );
+    Ok(())
+}
+
 fn render_form_data(
     form: &mut HashMap<String, FormDataValue>,
     key: &str,
@@ -120,14 +344,19 @@ fn render_form_data(
     }
 }
 
+/// A `key=v1&key=v2&...` field may expand into several entries (e.g. from a
+/// TOML array value or a repeated `key[]` binding), so every entry for `key`
+/// is substituted independently.
 fn render_form_urlencoded(
-    form: &mut HashMap<String, String>,
+    form: &mut HashMap<String, Vec<String>>,
     key: &str,
     placeholder: &str,
     val: &str,
 ) {
-    if let Some(v) = form.get_mut(key) {
-        replace_pointer!(v, placeholder, val);
+    if let Some(entries) = form.get_mut(key) {
+        for v in entries {
+            replace_pointer!(v, placeholder, val);
+        }
     }
 }
 
@@ -137,6 +366,23 @@ mod test {
     use http::{HeaderMap, Method, Version};
     use std::error::Error;
 
+    /// A minimal GET request to `https://example.com` with no headers, query
+    /// params, cookies, or body. Tests override only the fields they care
+    /// about, e.g. `Request { body, ..test_request() }`.
+    fn test_request() -> Request {
+        Request {
+            method: Method::GET,
+            url: "https://example.com".to_string(),
+            http_version: Version::HTTP_11,
+            metadata: HashMap::new(),
+            headers: HeaderMap::new(),
+            query_params: Vec::new(),
+            variables: HashMap::new(),
+            cookies: HashMap::new(),
+            body: rede_schema::Body::None,
+        }
+    }
+
     #[test]
     fn render() -> std::result::Result<(), Box<dyn Error>> {
         // todo replace by generated placeholders
@@ -169,14 +415,14 @@ mod test {
         query_params.push(("size".to_string(), "{{size}}".to_string()));
 
         let request = Request {
-            method: Method::GET,
             url: "https://example.com/{{id}}/{{name}}/{{id}}".to_string(),
-            http_version: Version::HTTP_11,
-            metadata: HashMap::new(),
             headers,
             query_params,
-            variables: HashMap::new(),
-            body: rede_schema::Body::None,
+            body: rede_schema::Body::Raw {
+                content: r#"{"id":"{{id}}","name":"{{name}}"}"#.to_string(),
+                mime: mime::APPLICATION_JSON,
+            },
+            ..test_request()
         };
 
         let rendered = renderer.render(request).unwrap();
@@ -190,9 +436,255 @@ mod test {
                 ("size".to_string(), "10".to_string()),
             ]
         );
+        assert_eq!(
+            rendered.body,
+            rede_schema::Body::Raw {
+                content: r#"{"id":"1","name":"test"}"#.to_string(),
+                mime: mime::APPLICATION_JSON,
+            }
+        );
         Ok(())
     }
 
+    #[test]
+    fn render_cookies() -> std::result::Result<(), Box<dyn Error>> {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::Cookies("session".to_string()), vec!["token"]);
+
+        let values = vec![("token".to_string(), "abc123".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("Cookie", "theme=dark".parse().unwrap());
+
+        let mut cookies = HashMap::new();
+        cookies.insert("session".to_string(), "{{token}}".to_string());
+
+        let request = Request {
+            headers,
+            cookies,
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(
+            rendered.headers["Cookie"].to_str()?,
+            "session=abc123; theme=dark"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn render_cookies_percent_encodes_special_characters() -> std::result::Result<(), Box<dyn Error>> {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::Cookies("session".to_string()), vec!["token"]);
+
+        let values = vec![("token".to_string(), "a;b=c".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let mut cookies = HashMap::new();
+        cookies.insert("session".to_string(), "{{token}}".to_string());
+
+        let request = Request {
+            cookies,
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(
+            rendered.headers["Cookie"].to_str()?,
+            "session=a%3Bb%3Dc"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn percent_encode_cookie() {
+        assert_eq!(super::percent_encode_cookie("a;b=c"), "a%3Bb%3Dc");
+        assert_eq!(super::percent_encode_cookie("a b"), "a%20b");
+        assert_eq!(super::percent_encode_cookie("safe-_.~09AZaz"), "safe-_.~09AZaz");
+    }
+
+    #[test]
+    fn render_validated_checks_resolved_value_not_placeholder_name() {
+        // The rule is keyed by the *field* name ("order"), and "order"'s
+        // value is built from a placeholder with a different name ("field")
+        // mixed with literal text, e.g. `order = "{{field}}:asc"`.
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::QueryParams("order".to_string()), vec!["field"]);
+
+        let values = vec![("field".to_string(), "id".to_string())];
+        let mut rules = HashMap::new();
+        rules.insert(
+            Location::QueryParams("order".to_string()),
+            "matches(\"^[a-z]+:asc$\")".parse().unwrap(),
+        );
+        let renderer = Renderer::new(placeholders, &values).with_rules(rules);
+
+        let request = Request {
+            query_params: vec![("order".to_string(), "{{field}}:asc".to_string())],
+            ..test_request()
+        };
+
+        assert!(renderer.render_validated(request).is_ok());
+    }
+
+    #[test]
+    fn render_validated_fails_when_rule_violated() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::QueryParams("order".to_string()), vec!["field"]);
+
+        let values = vec![("field".to_string(), "ID".to_string())];
+        let mut rules = HashMap::new();
+        rules.insert(
+            Location::QueryParams("order".to_string()),
+            "matches(\"^[a-z]+:asc$\")".parse().unwrap(),
+        );
+        let renderer = Renderer::new(placeholders, &values).with_rules(rules);
+
+        let request = Request {
+            query_params: vec![("order".to_string(), "{{field}}:asc".to_string())],
+            ..test_request()
+        };
+
+        assert!(renderer.render_validated(request).is_err());
+    }
+
+    #[test]
+    fn render_validated_does_not_leak_rules_across_locations() {
+        // A query-param rule and a form-data rule both named "name", with
+        // contradictory constraints: only the rule attached to the field's
+        // own Location should apply to it.
+        let placeholders = Placeholders::default();
+        let values: Vec<(String, String)> = vec![];
+
+        let mut rules = HashMap::new();
+        rules.insert(
+            Location::QueryParams("name".to_string()),
+            "one_of(\"alice\")".parse().unwrap(),
+        );
+        rules.insert(
+            Location::BodyForm("name".to_string()),
+            "one_of(\"bob\")".parse().unwrap(),
+        );
+        let renderer = Renderer::new(placeholders, &values).with_rules(rules);
+
+        let mut form = HashMap::new();
+        form.insert("name".to_string(), FormDataValue::Text("bob".to_string()));
+
+        let request = Request {
+            query_params: vec![("name".to_string(), "alice".to_string())],
+            body: rede_schema::Body::FormData(form),
+            ..test_request()
+        };
+
+        assert!(renderer.render_validated(request).is_ok());
+    }
+
+    #[test]
+    fn render_body_raw_escapes_json() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::Body, vec!["name"]);
+
+        let values = vec![("name".to_string(), "a\"b\\c".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let request = Request {
+            body: rede_schema::Body::Raw {
+                content: r#"{"name":"{{name}}"}"#.to_string(),
+                mime: mime::APPLICATION_JSON,
+            },
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(
+            rendered.body,
+            rede_schema::Body::Raw {
+                content: r#"{"name":"a\"b\\c"}"#.to_string(),
+                mime: mime::APPLICATION_JSON,
+            }
+        );
+    }
+
+    #[test]
+    fn render_body_raw_escapes_structured_syntax_json_suffix() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::Body, vec!["name"]);
+
+        let values = vec![("name".to_string(), "a\"b".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let request = Request {
+            body: rede_schema::Body::Raw {
+                content: r#"{"name":"{{name}}"}"#.to_string(),
+                mime: "application/problem+json".parse().unwrap(),
+            },
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(
+            rendered.body,
+            rede_schema::Body::Raw {
+                content: r#"{"name":"a\"b"}"#.to_string(),
+                mime: "application/problem+json".parse().unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn render_body_raw_non_json_is_verbatim() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::Body, vec!["name"]);
+
+        let values = vec![("name".to_string(), "a\"b".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let request = Request {
+            body: rede_schema::Body::Raw {
+                content: "name={{name}}".to_string(),
+                mime: mime::TEXT_PLAIN_UTF_8,
+            },
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(
+            rendered.body,
+            rede_schema::Body::Raw {
+                content: "name=a\"b".to_string(),
+                mime: mime::TEXT_PLAIN_UTF_8,
+            }
+        );
+    }
+
+    #[test]
+    fn render_body_binary_substitutes_path() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::Body, vec!["dir"]);
+
+        let values = vec![("dir".to_string(), "/tmp".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let request = Request {
+            body: rede_schema::Body::Binary {
+                path: "{{dir}}/file.bin".to_string(),
+                mime: mime::APPLICATION_OCTET_STREAM,
+            },
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(
+            rendered.body,
+            rede_schema::Body::Binary {
+                path: "/tmp/file.bin".to_string(),
+                mime: mime::APPLICATION_OCTET_STREAM,
+            }
+        );
+    }
+
     #[test]
     fn render_form_data() {
         let mut form = HashMap::new();
@@ -212,16 +704,72 @@ mod test {
         assert_eq!(form["file"], FormDataValue::File("/tmp/file".to_string()));
     }
 
+    #[test]
+    fn percent_encode_form() {
+        assert_eq!(super::percent_encode_form("a&b=c"), "a%26b%3Dc");
+        assert_eq!(super::percent_encode_form("a b"), "a+b");
+        assert_eq!(super::percent_encode_form("safe-_.~09AZaz"), "safe-_.~09AZaz");
+    }
+
+    #[test]
+    fn json_escape() {
+        assert_eq!(super::json_escape(r#"a"b\c"#), r#"a\"b\\c"#);
+        assert_eq!(super::json_escape("a\nb"), "a\\nb");
+    }
+
+    #[test]
+    fn render_query_params_percent_encodes_values() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::QueryParams("q".to_string()), vec!["q"]);
+
+        let values = vec![("q".to_string(), "a&b c".to_string())];
+        let renderer = Renderer::new(placeholders, &values);
+
+        let request = Request {
+            query_params: vec![("q".to_string(), "{{q}}".to_string())],
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(rendered.query_params, vec![("q".to_string(), "a%26b+c".to_string())]);
+    }
+
+    #[test]
+    fn render_query_params_can_skip_percent_encoding() {
+        let mut placeholders = Placeholders::default();
+        placeholders.add_all(&Location::QueryParams("q".to_string()), vec!["q"]);
+
+        let values = vec![("q".to_string(), "a&b c".to_string())];
+        let renderer =
+            Renderer::new(placeholders, &values).with_options(RenderOptions {
+                skip_percent_encoding: true,
+            });
+
+        let request = Request {
+            query_params: vec![("q".to_string(), "{{q}}".to_string())],
+            ..test_request()
+        };
+
+        let rendered = renderer.render(request).unwrap();
+        assert_eq!(rendered.query_params, vec![("q".to_string(), "a&b c".to_string())]);
+    }
+
     #[test]
     fn render_form_urlencoded() {
         let mut form = HashMap::new();
-        form.insert("page".to_string(), "{{page}}".to_string());
-        form.insert("order".to_string(), "{{field}}:asc".to_string());
+        form.insert("page".to_string(), vec!["{{page}}".to_string()]);
+        form.insert("order".to_string(), vec!["{{field}}:asc".to_string()]);
+        form.insert(
+            "tags".to_string(),
+            vec!["{{tag}}".to_string(), "{{tag}}-2".to_string()],
+        );
 
         super::render_form_urlencoded(&mut form, "page", "{{page}}", "10");
         super::render_form_urlencoded(&mut form, "order", "{{field}}", "id");
+        super::render_form_urlencoded(&mut form, "tags", "{{tag}}", "a");
 
-        assert_eq!(form["page"], "10".to_string());
-        assert_eq!(form["order"], "id:asc".to_string());
+        assert_eq!(form["page"], vec!["10".to_string()]);
+        assert_eq!(form["order"], vec!["id:asc".to_string()]);
+        assert_eq!(form["tags"], vec!["a".to_string(), "a-2".to_string()]);
     }
 }
@@ -0,0 +1,199 @@
+use crate::placeholders::Location;
+use crate::Placeholders;
+use once_cell::sync::Lazy;
+use rede_schema::body::FormDataValue;
+use rede_schema::{Body, Request};
+use regex::Regex;
+use std::collections::HashSet;
+
+/// Matches a `{{ identifier }}` placeholder, capturing the identifier.
+static PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap());
+
+impl Placeholders {
+    /// Discovers every `{{identifier}}` placeholder in `request` (url,
+    /// headers, query params, cookies and form/raw body) and returns the
+    /// populated [`Placeholders`] together with the set of distinct variable
+    /// names found, so a caller can prompt for exactly the values a request
+    /// needs.
+    #[must_use]
+    pub fn scan(request: &Request) -> (Self, HashSet<String>) {
+        let mut placeholders = Self::default();
+        let mut names = HashSet::new();
+
+        scan_str(&request.url, &Location::Url, &mut placeholders, &mut names);
+
+        for (name, value) in &request.headers {
+            if let Ok(value) = value.to_str() {
+                scan_str(
+                    value,
+                    &Location::Headers(name.clone()),
+                    &mut placeholders,
+                    &mut names,
+                );
+            }
+        }
+
+        for (key, value) in &request.query_params {
+            scan_str(
+                value,
+                &Location::QueryParams(key.clone()),
+                &mut placeholders,
+                &mut names,
+            );
+        }
+
+        for (key, value) in &request.cookies {
+            scan_str(
+                value,
+                &Location::Cookies(key.clone()),
+                &mut placeholders,
+                &mut names,
+            );
+        }
+
+        match &request.body {
+            Body::FormData(form) => {
+                for (key, value) in form {
+                    let FormDataValue::Text(value) | FormDataValue::File(value) = value;
+                    scan_str(
+                        value,
+                        &Location::BodyForm(key.clone()),
+                        &mut placeholders,
+                        &mut names,
+                    );
+                }
+            }
+            Body::XFormUrlEncoded(form) => {
+                for (key, values) in form {
+                    for value in values {
+                        scan_str(
+                            value,
+                            &Location::BodyForm(key.clone()),
+                            &mut placeholders,
+                            &mut names,
+                        );
+                    }
+                }
+            }
+            Body::Raw { content, .. } => {
+                scan_str(content, &Location::Body, &mut placeholders, &mut names);
+            }
+            Body::Binary { path, .. } => {
+                scan_str(path, &Location::Body, &mut placeholders, &mut names);
+            }
+            Body::None => {}
+        }
+
+        (placeholders, names)
+    }
+}
+
+/// Records every placeholder found in `value` under `location`, leaving
+/// variables with no match untouched (they simply aren't recorded).
+fn scan_str(
+    value: &str,
+    location: &Location,
+    placeholders: &mut Placeholders,
+    names: &mut HashSet<String>,
+) {
+    for capture in PLACEHOLDER.captures_iter(value) {
+        let name = &capture[1];
+        placeholders.add_all(location, vec![name]);
+        names.insert(name.to_string());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use http::{HeaderMap, Method, Version};
+    use std::collections::HashMap;
+
+    #[test]
+    fn scan_finds_repeated_and_distinct_locations() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Authorization", "Bearer {{token}}".parse().unwrap());
+
+        let request = Request {
+            method: Method::GET,
+            url: "https://example.com/{{id}}/{{id}}".to_string(),
+            http_version: Version::HTTP_11,
+            metadata: HashMap::new(),
+            headers,
+            query_params: vec![("page".to_string(), "{{page}}".to_string())],
+            variables: HashMap::new(),
+            cookies: HashMap::new(),
+            body: Body::Raw {
+                content: r#"{"id":"{{id}}"}"#.to_string(),
+                mime: mime::APPLICATION_JSON,
+            },
+        };
+
+        let (placeholders, names) = Placeholders::scan(&request);
+
+        assert_eq!(
+            names,
+            HashSet::from([
+                "id".to_string(),
+                "token".to_string(),
+                "page".to_string(),
+            ])
+        );
+
+        let (_, id_locations) = placeholders
+            .iter()
+            .find(|(k, _)| k.as_str() == "id")
+            .unwrap();
+        assert_eq!(id_locations.len(), 3);
+    }
+
+    #[test]
+    fn scan_finds_placeholders_in_every_expanded_urlencoded_entry() {
+        let mut form = HashMap::new();
+        form.insert(
+            "tags".to_string(),
+            vec!["{{tag}}".to_string(), "static".to_string()],
+        );
+
+        let request = Request {
+            method: Method::GET,
+            url: "https://example.com".to_string(),
+            http_version: Version::HTTP_11,
+            metadata: HashMap::new(),
+            headers: HeaderMap::new(),
+            query_params: Vec::new(),
+            variables: HashMap::new(),
+            cookies: HashMap::new(),
+            body: Body::XFormUrlEncoded(form),
+        };
+
+        let (placeholders, names) = Placeholders::scan(&request);
+
+        assert_eq!(names, HashSet::from(["tag".to_string()]));
+        let (_, locations) = placeholders
+            .iter()
+            .find(|(k, _)| k.as_str() == "tag")
+            .unwrap();
+        assert_eq!(locations.len(), 1);
+    }
+
+    #[test]
+    fn scan_leaves_unmatched_variables_untouched() {
+        let request = Request {
+            method: Method::GET,
+            url: "https://example.com/no-placeholders-here".to_string(),
+            http_version: Version::HTTP_11,
+            metadata: HashMap::new(),
+            headers: HeaderMap::new(),
+            query_params: Vec::new(),
+            variables: HashMap::new(),
+            cookies: HashMap::new(),
+            body: Body::None,
+        };
+
+        let (placeholders, names) = Placeholders::scan(&request);
+        assert!(names.is_empty());
+        assert_eq!(placeholders.iter().count(), 0);
+    }
+}